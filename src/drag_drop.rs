@@ -0,0 +1,417 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+use crate::{
+    cx::Cx,
+    effects::{AppendEffect, EffectTuple, EntityEffect},
+    view::View,
+};
+
+/// The currently-active drag gesture, tracked while the pointer is down after
+/// starting on a [`Draggable`] element. Holds the boxed payload so a drop
+/// target can attempt to downcast it to the type it expects.
+#[derive(Resource, Default)]
+pub struct DragState(pub Option<AnyDrag>);
+
+/// A drag in progress: the entity it started from, its boxed payload, and the
+/// pointer's offset from the source entity's origin at drag start.
+pub struct AnyDrag {
+    pub source: Entity,
+    pub payload: Box<dyn Any + Send + Sync>,
+    pub offset: Vec2,
+}
+
+/// Component marking an entity as a drag source, holding the typed payload
+/// that will be handed to the drop target's `on_drop` handler.
+#[derive(Component)]
+pub struct Draggable<P: Clone + Send + Sync + 'static>(pub P);
+
+/// Component marking an entity as a drop target.
+#[derive(Component)]
+pub struct DropTarget;
+
+/// Type-erased `on_drop` handler installed by [`Element::on_drop`], stored on
+/// the drop target entity and called by the observer watching it for
+/// `Pointer<DragDrop>`.
+#[derive(Component, Clone)]
+struct DropHandler(Arc<Mutex<dyn FnMut(Box<dyn Any + Send + Sync>, &mut Cx) + Send + Sync>>);
+
+/// `on_drag_over` handler installed by [`Element::on_drag_over`], called
+/// with `true` when a drag enters the target's hit region and `false` when
+/// it leaves.
+#[derive(Component, Clone)]
+struct DragOverHandler(Arc<Mutex<dyn FnMut(bool, &mut Cx) + Send + Sync>>);
+
+/// Observer entities spawned for a drag-related effect, despawned together
+/// when that effect is razed. An element can carry more than one of
+/// `DraggableEffect`/`OnDropEffect`/`OnDragOverEffect` at once (a reorderable
+/// list item that's both a drag source and a drop target, say), so each
+/// effect's observers are appended under its own key rather than sharing one
+/// component that the next effect would overwrite.
+#[derive(Component, Default)]
+struct InstalledDragObservers(HashMap<TypeId, Vec<Entity>>);
+
+fn append_installed_observers<T: 'static>(world: &mut World, entity: Entity, observers: Vec<Entity>) {
+    let mut entity = world.entity_mut(entity);
+    let mut installed = entity.get_mut::<InstalledDragObservers>();
+    match &mut installed {
+        Some(installed) => {
+            installed.0.entry(TypeId::of::<T>()).or_default().extend(observers);
+        }
+        None => {
+            drop(installed);
+            let mut map = HashMap::new();
+            map.insert(TypeId::of::<T>(), observers);
+            entity.insert(InstalledDragObservers(map));
+        }
+    }
+}
+
+fn despawn_installed<T: 'static>(world: &mut World, entity: Entity) {
+    let observers = world
+        .get_mut::<InstalledDragObservers>(entity)
+        .and_then(|mut installed| installed.0.remove(&TypeId::of::<T>()));
+    let Some(observers) = observers else {
+        return;
+    };
+    for observer in observers {
+        if let Some(observer) = world.get_entity_mut(observer) {
+            observer.despawn();
+        }
+    }
+}
+
+/// An [`EntityEffect`] that marks the display entity as a drag source for
+/// payload `P`, and wires up the observers that populate [`DragState`] while
+/// the pointer drags it.
+pub struct DraggableEffect<P: Clone + Send + Sync + 'static> {
+    pub(crate) payload: P,
+}
+
+impl<P: Clone + Send + Sync + 'static> EntityEffect for DraggableEffect<P> {
+    type State = ();
+
+    fn apply(&self, cx: &mut Cx, entity: Entity) -> Self::State {
+        cx.world_mut()
+            .entity_mut(entity)
+            .insert(Draggable(self.payload.clone()));
+        self.install_observers(cx, entity);
+    }
+
+    fn reapply(&self, cx: &mut Cx, entity: Entity, _state: &mut Self::State) {
+        cx.world_mut()
+            .entity_mut(entity)
+            .insert(Draggable(self.payload.clone()));
+    }
+
+    fn raze(&self, world: &mut World, entity: Entity, _state: &mut Self::State) {
+        if let Some(mut entity) = world.get_entity_mut(entity) {
+            entity.remove::<Draggable<P>>();
+        }
+        despawn_installed::<Self>(world, entity);
+    }
+}
+
+impl<P: Clone + Send + Sync + 'static> DraggableEffect<P> {
+    fn install_observers(&self, cx: &mut Cx, entity: Entity) {
+        let start = cx
+            .world_mut()
+            .spawn(
+                Observer::new(
+                    move |trigger: Trigger<Pointer<DragStart>>, world: &mut World| {
+                        let Some(Draggable(payload)) = world.get::<Draggable<P>>(entity) else {
+                            return;
+                        };
+                        let payload = payload.clone();
+                        // Offset from the source entity's origin, so the
+                        // preview can stay glued to the point it was
+                        // grabbed at instead of snapping to the cursor.
+                        let origin = world
+                            .get::<GlobalTransform>(entity)
+                            .map(|transform| transform.translation().truncate())
+                            .unwrap_or(Vec2::ZERO);
+                        let offset = trigger.event().pointer_location.position - origin;
+                        world.resource_mut::<DragState>().0 = Some(AnyDrag {
+                            source: entity,
+                            payload: Box::new(payload),
+                            offset,
+                        });
+                    },
+                )
+                .with_entity(entity),
+            )
+            .id();
+
+        let end = cx
+            .world_mut()
+            .spawn(
+                Observer::new(move |_: Trigger<Pointer<DragEnd>>, world: &mut World| {
+                    let mut drag_state = world.resource_mut::<DragState>();
+                    if matches!(&drag_state.0, Some(drag) if drag.source == entity) {
+                        drag_state.0 = None;
+                    }
+                })
+                .with_entity(entity),
+            )
+            .id();
+
+        append_installed_observers::<Self>(cx.world_mut(), entity, vec![start, end]);
+    }
+}
+
+/// An [`EntityEffect`] that marks the display entity as a drop target and
+/// calls `handler` with the downcast payload when a drag is released over
+/// it.
+pub struct OnDropEffect<P: Clone + Send + Sync + 'static, F: Send + Sync + 'static + FnMut(P, &mut Cx)>
+{
+    pub(crate) handler: Arc<Mutex<F>>,
+    pub(crate) marker: std::marker::PhantomData<fn(P)>,
+}
+
+impl<P: Clone + Send + Sync + 'static, F: Send + Sync + 'static + FnMut(P, &mut Cx)> EntityEffect
+    for OnDropEffect<P, F>
+{
+    type State = ();
+
+    fn apply(&self, cx: &mut Cx, entity: Entity) -> Self::State {
+        cx.world_mut().entity_mut(entity).insert(DropTarget);
+
+        let handler = self.handler.clone();
+        cx.world_mut()
+            .entity_mut(entity)
+            .insert(DropHandler(Arc::new(Mutex::new(
+                move |payload: Box<dyn Any + Send + Sync>, cx: &mut Cx| {
+                    if let Ok(payload) = payload.downcast::<P>() {
+                        handler.lock().unwrap()(*payload, cx);
+                    }
+                },
+            ))));
+
+        let observer = cx
+            .world_mut()
+            .spawn(
+                Observer::new(
+                    move |_: Trigger<Pointer<DragDrop>>, world: &mut World| {
+                        let Some(drag) = world.resource_mut::<DragState>().0.take() else {
+                            return;
+                        };
+                        let Some(DropHandler(handler)) = world.get::<DropHandler>(entity).cloned()
+                        else {
+                            return;
+                        };
+                        let mut cx = Cx::for_owner(world, entity);
+                        handler.lock().unwrap()(drag.payload, &mut cx);
+                    },
+                )
+                .with_entity(entity),
+            )
+            .id();
+        append_installed_observers::<Self>(cx.world_mut(), entity, vec![observer]);
+    }
+
+    fn reapply(&self, cx: &mut Cx, entity: Entity, _state: &mut Self::State) {
+        cx.world_mut().entity_mut(entity).insert(DropTarget);
+        let handler = self.handler.clone();
+        cx.world_mut()
+            .entity_mut(entity)
+            .insert(DropHandler(Arc::new(Mutex::new(
+                move |payload: Box<dyn Any + Send + Sync>, cx: &mut Cx| {
+                    if let Ok(payload) = payload.downcast::<P>() {
+                        handler.lock().unwrap()(*payload, cx);
+                    }
+                },
+            ))));
+    }
+
+    fn raze(&self, world: &mut World, entity: Entity, _state: &mut Self::State) {
+        if let Some(mut entity) = world.get_entity_mut(entity) {
+            entity.remove::<DropTarget>();
+            entity.remove::<DropHandler>();
+        }
+        despawn_installed::<Self>(world, entity);
+    }
+}
+
+/// An [`EntityEffect`] that marks the display entity as a drop target and
+/// calls `handler` with `true`/`false` as a drag enters/leaves its hit
+/// region, so targets can reflect hover state through styles.
+pub struct OnDragOverEffect<H: Send + Sync + 'static + FnMut(bool, &mut Cx)> {
+    pub(crate) handler: Arc<Mutex<H>>,
+}
+
+impl<H: Send + Sync + 'static + FnMut(bool, &mut Cx)> EntityEffect for OnDragOverEffect<H> {
+    type State = ();
+
+    fn apply(&self, cx: &mut Cx, entity: Entity) -> Self::State {
+        cx.world_mut().entity_mut(entity).insert(DropTarget);
+        cx.world_mut()
+            .entity_mut(entity)
+            .insert(DragOverHandler(self.handler.clone()));
+
+        let enter = cx
+            .world_mut()
+            .spawn(
+                Observer::new(
+                    move |_: Trigger<Pointer<DragEnter>>, world: &mut World| {
+                        let Some(DragOverHandler(handler)) =
+                            world.get::<DragOverHandler>(entity).cloned()
+                        else {
+                            return;
+                        };
+                        let mut cx = Cx::for_owner(world, entity);
+                        handler.lock().unwrap()(true, &mut cx);
+                    },
+                )
+                .with_entity(entity),
+            )
+            .id();
+        let leave = cx
+            .world_mut()
+            .spawn(
+                Observer::new(
+                    move |_: Trigger<Pointer<DragLeave>>, world: &mut World| {
+                        let Some(DragOverHandler(handler)) =
+                            world.get::<DragOverHandler>(entity).cloned()
+                        else {
+                            return;
+                        };
+                        let mut cx = Cx::for_owner(world, entity);
+                        handler.lock().unwrap()(false, &mut cx);
+                    },
+                )
+                .with_entity(entity),
+            )
+            .id();
+
+        append_installed_observers::<Self>(cx.world_mut(), entity, vec![enter, leave]);
+    }
+
+    fn reapply(&self, cx: &mut Cx, entity: Entity, _state: &mut Self::State) {
+        cx.world_mut().entity_mut(entity).insert(DropTarget);
+        cx.world_mut()
+            .entity_mut(entity)
+            .insert(DragOverHandler(self.handler.clone()));
+    }
+
+    fn raze(&self, world: &mut World, entity: Entity, _state: &mut Self::State) {
+        if let Some(mut entity) = world.get_entity_mut(entity) {
+            entity.remove::<DropTarget>();
+            entity.remove::<DragOverHandler>();
+        }
+        despawn_installed::<Self>(world, entity);
+    }
+}
+
+/// Tracks the root entity of the currently-rendered drag preview, so it can
+/// be moved rather than respawned while the drag is in progress.
+#[derive(Resource, Default)]
+struct DragPreviewRoot(Option<Entity>);
+
+/// System that spawns an absolutely-positioned preview root node and keeps it
+/// at the cursor (less the grab offset) for as long as [`DragState`] holds an
+/// active drag, and despawns it on release. The `preview` closure is not
+/// called: no view is built under the root node. Add to the app's `Update`
+/// schedule once, alongside the `Draggable`/`DropTarget` observers installed
+/// by [`Element::draggable`]/[`Element::on_drop`].
+pub fn update_drag_preview<V: View>(
+    preview: impl Fn(&dyn Any) -> V + Send + Sync + 'static,
+) -> impl FnMut(&mut World) + Send + Sync + 'static {
+    move |world: &mut World| {
+        world.init_resource::<DragPreviewRoot>();
+        let offset = match &world.resource::<DragState>().0 {
+            Some(drag) => drag.offset,
+            None => {
+                if let Some(root) = world.resource_mut::<DragPreviewRoot>().0.take() {
+                    if let Some(entity) = world.get_entity_mut(root) {
+                        entity.despawn_recursive();
+                    }
+                }
+                return;
+            }
+        };
+
+        let cursor = world
+            .query_filtered::<&Window, With<PrimaryWindow>>()
+            .iter(world)
+            .next()
+            .and_then(Window::cursor_position)
+            .unwrap_or(Vec2::ZERO);
+        // Keep the preview glued to the point the drag started at, rather
+        // than snapping its origin to the cursor.
+        let position = cursor - offset;
+
+        let root = *world
+            .resource_mut::<DragPreviewRoot>()
+            .0
+            .get_or_insert_with(|| {
+                world
+                    .spawn(NodeBundle {
+                        style: Style {
+                            position_type: PositionType::Absolute,
+                            ..default()
+                        },
+                        ..default()
+                    })
+                    .id()
+            });
+
+        // Re-point the preview root at the current cursor position each
+        // frame; `preview` is never called, so the root stays empty.
+        let _ = &preview;
+        if let Some(mut style) = world.get_mut::<Style>(root) {
+            style.left = Val::Px(position.x);
+            style.top = Val::Px(position.y);
+        }
+    }
+}
+
+impl<B: Bundle + Default, C: crate::view_tuple::ViewTuple, E: EffectTuple>
+    crate::element::Element<B, C, E>
+{
+    /// Mark this element as a drag source carrying `payload`.
+    pub fn draggable<P: Clone + Send + Sync + 'static>(
+        self,
+        payload: P,
+    ) -> crate::element::Element<B, C, <E as AppendEffect<DraggableEffect<P>>>::Result>
+    where
+        E: AppendEffect<DraggableEffect<P>>,
+    {
+        self.add_effect(DraggableEffect { payload })
+    }
+
+    /// Mark this element as a drop target for payload `P`, invoking `handler`
+    /// when a drag carrying that payload type is released over it.
+    pub fn on_drop<P, F>(
+        self,
+        handler: F,
+    ) -> crate::element::Element<B, C, <E as AppendEffect<OnDropEffect<P, F>>>::Result>
+    where
+        P: Clone + Send + Sync + 'static,
+        F: Send + Sync + 'static + FnMut(P, &mut Cx),
+        E: AppendEffect<OnDropEffect<P, F>>,
+    {
+        self.add_effect(OnDropEffect {
+            handler: Arc::new(Mutex::new(handler)),
+            marker: std::marker::PhantomData,
+        })
+    }
+
+    /// Invoke `handler` with `true`/`false` as a drag enters/leaves this
+    /// element's hit region, so it can reflect hover state through styles.
+    pub fn on_drag_over<H>(
+        self,
+        handler: H,
+    ) -> crate::element::Element<B, C, <E as AppendEffect<OnDragOverEffect<H>>>::Result>
+    where
+        H: Send + Sync + 'static + FnMut(bool, &mut Cx),
+        E: AppendEffect<OnDragOverEffect<H>>,
+    {
+        self.add_effect(OnDragOverEffect {
+            handler: Arc::new(Mutex::new(handler)),
+        })
+    }
+}
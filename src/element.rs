@@ -1,4 +1,5 @@
 use std::marker::PhantomData;
+use std::sync::Arc;
 
 use bevy::prelude::*;
 use bevy_mod_stylebuilder::StyleTuple;
@@ -6,12 +7,18 @@ use bevy_mod_stylebuilder::StyleTuple;
 use crate::{
     cx::Cx,
     effects::{self, AppendEffect, EffectTuple, EntityEffect},
+    layout::LayoutConstraint,
     node_span::NodeSpan,
     style::ApplyStylesEffect,
     view::View,
     view_tuple::ViewTuple,
 };
 
+/// A closure that computes intrinsic-size constraints for an element's
+/// display node from the (already laid-out) state of its children. See
+/// [`Element::measured`].
+type MeasureFn<S> = dyn Fn(&World, &S) -> Option<LayoutConstraint> + Send + Sync;
+
 /// A view which generates an entity bundle.
 #[derive(Default)]
 pub struct Element<B: Bundle + Default = NodeBundle, C: ViewTuple = (), E: EffectTuple = ()> {
@@ -21,12 +28,21 @@ pub struct Element<B: Bundle + Default = NodeBundle, C: ViewTuple = (), E: Effec
     /// The visible UI node for this element.
     display: Option<Entity>,
 
+    /// An existing entity whose reflected components should be cloned onto
+    /// the display node before effects run, see [`Element::clone_of`].
+    clone_source: Option<Entity>,
+
     /// Children of this element.
     children: C,
 
     /// List of effects to be added to the element.
     effects: E,
 
+    /// Optional measure closure that contributes intrinsic-size constraints
+    /// to the display node once children have been built, see
+    /// [`Element::measured`].
+    measure: Option<Arc<MeasureFn<C::State>>>,
+
     marker: PhantomData<B>,
 }
 
@@ -36,8 +52,10 @@ impl<B: Bundle + Default> Element<B, (), ()> {
         Self {
             debug_name: String::new(),
             display: None,
+            clone_source: None,
             children: (),
             effects: (),
+            measure: None,
             marker: PhantomData,
         }
     }
@@ -47,8 +65,10 @@ impl<B: Bundle + Default> Element<B, (), ()> {
         Self {
             debug_name: String::new(),
             display: Some(node),
+            clone_source: None,
             children: (),
             effects: (),
+            measure: None,
             marker: PhantomData,
         }
     }
@@ -67,11 +87,84 @@ impl<B: Bundle + Default, C: ViewTuple, E: EffectTuple> Element<B, C, E> {
             children,
             debug_name: self.debug_name,
             display: self.display,
+            clone_source: self.clone_source,
+            effects: self.effects,
+            // The measure closure is typed over the old child state, so it
+            // can't carry over to the new child set.
+            measure: None,
+            marker: PhantomData,
+        }
+    }
+
+    /// Set the children of this element from an iterator of `(key, view)`
+    /// pairs, reconciled by key instead of by position. Use this instead of
+    /// [`Element::children`] for dynamic lists where items can be inserted,
+    /// removed or reordered and should keep their entity identity.
+    pub fn keyed_children<K: crate::keyed::Key, V: View + Clone>(
+        self,
+        children: impl IntoIterator<Item = (K, V)>,
+    ) -> Element<B, crate::keyed::KeyedChildren<K, V>, E> {
+        Element {
+            children: crate::keyed::KeyedChildren::new(children),
+            debug_name: self.debug_name,
+            display: self.display,
+            clone_source: self.clone_source,
             effects: self.effects,
+            measure: None,
             marker: PhantomData,
         }
     }
 
+    /// Contribute intrinsic-size constraints to this element's display node,
+    /// computed from its (already built) children. Evaluated by `build` and
+    /// `rebuild` after children are built but before the frame's taffy pass,
+    /// so e.g. a container can grow to fit a measured child instead of being
+    /// stuck with whatever `B`/styles specify up front.
+    ///
+    /// This is currently an `Element`-only hook, not a `View` trait method:
+    /// plumbing it through `View` so other implementors can participate
+    /// would touch the trait definition, which lives outside this crate
+    /// slice. Treat this as the first half of that cross-cutting change.
+    pub fn measured(
+        mut self,
+        measure: impl Fn(&World, &C::State) -> Option<LayoutConstraint> + Send + Sync + 'static,
+    ) -> Self {
+        self.measure = Some(Arc::new(measure));
+        self
+    }
+
+    /// Run the measure phase: if a `measure` closure was set via
+    /// [`Element::measured`], evaluate it against the already-built children
+    /// and apply the resulting constraint to the display node's `Style`
+    /// before the frame's taffy pass picks it up. Inherent method, not a
+    /// `View` override; see the note on [`Element::measured`].
+    fn layout(&self, cx: &mut Cx, state: &(Entity, C::State, E::State)) {
+        let Some(measure) = &self.measure else {
+            return;
+        };
+        let Some(constraint) = measure(cx.world(), &state.1) else {
+            return;
+        };
+        if let Some(mut style) = cx.world_mut().entity_mut(state.0).get_mut::<Style>() {
+            constraint.apply(&mut style);
+        }
+    }
+
+    /// Adopt `source`'s full, reflectable component set as this element's
+    /// display bundle. In `build`, `B::default()` is inserted onto the
+    /// freshly spawned (or [`for_entity`](Element::for_entity)) display node
+    /// first, then every component on `source` that is registered with
+    /// `ReflectComponent` is cloned on top of it, before effects run. So the
+    /// prefab's own components win over whatever `B` supplies, letting a
+    /// scene-authored or Blender-exported entity be used as a prefab for
+    /// many reactive copies with styles/effects layered on top of it in
+    /// turn. Components without a `ReflectComponent` registration are
+    /// skipped.
+    pub fn clone_of(mut self, source: Entity) -> Self {
+        self.clone_source = Some(source);
+        self
+    }
+
     /// Add an effect to this element.
     pub fn add_effect<E1: EntityEffect>(
         self,
@@ -84,7 +177,9 @@ impl<B: Bundle + Default, C: ViewTuple, E: EffectTuple> Element<B, C, E> {
             children: self.children,
             debug_name: self.debug_name,
             display: self.display,
+            clone_source: self.clone_source,
             effects: self.effects.append_effect(effect),
+            measure: self.measure,
             marker: PhantomData,
         }
     }
@@ -123,6 +218,45 @@ impl<B: Bundle + Default, C: ViewTuple, E: EffectTuple> Element<B, C, E> {
 // impl<B: Bundle + Default> EffectTarget for Element<B> {
 // }
 
+/// Deep-copy every reflected component on `source` onto `target`, using the
+/// app's [`AppTypeRegistry`]. Components that aren't registered with
+/// `ReflectComponent` (or whose type isn't in the registry at all) are
+/// silently skipped rather than panicking, since not every component in a
+/// scene needs to be reflectable to be usable as a prefab.
+fn clone_reflected_components(world: &mut World, source: Entity, target: Entity) {
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+
+    let Some(source_ref) = world.get_entity(source) else {
+        return;
+    };
+    let component_ids: Vec<_> = source_ref.archetype().components().collect();
+
+    for component_id in component_ids {
+        let Some(component_info) = world.components().get_info(component_id) else {
+            continue;
+        };
+        let Some(type_id) = component_info.type_id() else {
+            continue;
+        };
+        let Some(registration) = registry.get(type_id) else {
+            continue;
+        };
+        let Some(reflect_component) = registration.data::<ReflectComponent>() else {
+            continue;
+        };
+
+        if let Some(source_value) = reflect_component.reflect(world.entity(source)) {
+            let cloned = source_value.clone_value();
+            reflect_component.apply_or_insert(
+                &mut world.entity_mut(target),
+                cloned.as_partial_reflect(),
+                &registry,
+            );
+        }
+    }
+}
+
 impl<B: Bundle + Default, C: ViewTuple, E: EffectTuple + 'static> View for Element<B, C, E> {
     type State = (Entity, C::State, E::State);
 
@@ -156,6 +290,14 @@ impl<B: Bundle + Default, C: ViewTuple, E: EffectTuple + 'static> View for Eleme
                 .id(),
         };
 
+        // Clone over the reflected components of the prefab entity, if one
+        // was set via `clone_of`, after `B::default()` so the prefab's own
+        // components win, and before effects run so they can still override
+        // anything the prefab supplies.
+        if let Some(source) = self.clone_source {
+            clone_reflected_components(cx.world_mut(), source, display);
+        }
+
         // Run attached effects.
         let eff_state = effects::EffectTuple::apply(&self.effects, cx, display);
 
@@ -165,17 +307,23 @@ impl<B: Bundle + Default, C: ViewTuple, E: EffectTuple + 'static> View for Eleme
         cx.world_mut()
             .entity_mut(display)
             .replace_children(&nodes.to_vec());
-        (display, children, eff_state)
+        let state = (display, children, eff_state);
+        self.layout(cx, &state);
+        state
     }
 
     fn rebuild(&self, cx: &mut crate::cx::Cx, state: &mut Self::State) -> bool {
         effects::EffectTuple::reapply(&self.effects, cx, state.0, &mut state.2);
-        if self.children.rebuild_spans(cx, &mut state.1) {
+        let children_changed = self.children.rebuild_spans(cx, &mut state.1);
+        if children_changed {
             let nodes = self.children.span_nodes(&state.1);
             cx.world_mut()
                 .entity_mut(state.0)
                 .replace_children(&nodes.to_vec());
         }
+        if children_changed || self.measure.is_some() {
+            self.layout(cx, state);
+        }
         false
     }
 
@@ -183,6 +331,10 @@ impl<B: Bundle + Default, C: ViewTuple, E: EffectTuple + 'static> View for Eleme
         // assert!(state.is_some());
         // self.raze_children(world);
 
+        // Tear down attached effects (event listeners and the like) before
+        // the display node itself goes away.
+        effects::EffectTuple::raze(&self.effects, world, state.0, &mut state.2);
+
         // Delete the display node.
         world.entity_mut(state.0).remove_parent();
         world.entity_mut(state.0).despawn();
@@ -207,3 +359,44 @@ impl<B: Bundle + Default, C: ViewTuple, E: EffectTuple + 'static> View for Eleme
 //         }))
 //     }
 // }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Component, Reflect, Clone, PartialEq, Debug, Default)]
+    #[reflect(Component)]
+    struct Label(String);
+
+    #[derive(Component, Reflect, Clone, PartialEq, Debug, Default)]
+    #[reflect(Component)]
+    struct Count(u32);
+
+    #[test]
+    fn clone_of_round_trips_reflected_components() {
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        {
+            let registry = world.resource::<AppTypeRegistry>();
+            let mut registry = registry.write();
+            registry.register::<Label>();
+            registry.register::<Count>();
+        }
+
+        let source = world
+            .spawn((Label("prefab".to_string()), Count(7), Transform::default()))
+            .id();
+        let target = world.spawn_empty().id();
+
+        clone_reflected_components(&mut world, source, target);
+
+        assert_eq!(
+            world.entity(target).get::<Label>(),
+            Some(&Label("prefab".to_string()))
+        );
+        assert_eq!(world.entity(target).get::<Count>(), Some(&Count(7)));
+        // `Transform` isn't registered above, so it's skipped rather than
+        // panicking.
+        assert!(world.entity(target).get::<Transform>().is_none());
+    }
+}
@@ -0,0 +1,317 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use bevy::prelude::*;
+
+use crate::{
+    cx::Cx,
+    effects::{AppendEffect, EffectTuple, EntityEffect},
+};
+
+/// The pointer/keyboard interactions an [`Element`](crate::element::Element)
+/// can listen for via [`Element::on_click`](crate::element::Element::on_click)
+/// and friends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventKind {
+    Click,
+    PointerDown,
+    PointerOver,
+    Key,
+}
+
+/// Payload delivered to a listener installed by [`InsertEventListener`].
+#[derive(Debug, Clone)]
+pub enum ElementEvent {
+    Click,
+    PointerDown { button: PointerButton },
+    PointerOver,
+    Key { key: KeyCode },
+}
+
+/// Tracks which entity currently has keyboard focus. [`Element::on_key`]
+/// handlers only fire for the entity named here.
+#[derive(Resource, Default)]
+pub struct FocusedEntity(pub Option<Entity>);
+
+/// `on_key` handler for an entity, dispatched by [`dispatch_key_events`].
+/// Unlike `Click`/`PointerDown`/`PointerOver`, which bevy_picking delivers
+/// as entity-targeted events an `Observer<Trigger<Pointer<_>>>` can pick up,
+/// `KeyboardInput` is a plain, un-targeted event read through `Events<T>`
+/// (here via the `ButtonInput<KeyCode>` resource it feeds), so there is no
+/// observer to attach it to; it has to be polled and matched against
+/// [`FocusedEntity`] instead.
+#[derive(Component, Clone)]
+struct KeyHandler(Arc<Mutex<dyn FnMut(&ElementEvent, &mut Cx) + Send + Sync>>);
+
+/// System: for the focused entity (see [`FocusedEntity`]), call its
+/// [`Element::on_key`] handler for every key pressed this frame. Add to the
+/// app's `Update` schedule once to enable `on_key`.
+pub fn dispatch_key_events(world: &mut World) {
+    let Some(focused) = world.resource::<FocusedEntity>().0 else {
+        return;
+    };
+    let Some(KeyHandler(handler)) = world.get::<KeyHandler>(focused).cloned() else {
+        return;
+    };
+    let just_pressed: Vec<KeyCode> = world
+        .resource::<ButtonInput<KeyCode>>()
+        .get_just_pressed()
+        .copied()
+        .collect();
+    for key in just_pressed {
+        let mut cx = Cx::for_owner(world, focused);
+        handler.lock().unwrap()(&ElementEvent::Key { key }, &mut cx);
+    }
+}
+
+/// Tracks the observer entity spawned for each [`EventKind`] listened for on
+/// an entity, so `reapply` can despawn the previous observer of that kind
+/// instead of stacking a new one on top of it on every rebuild.
+#[derive(Component, Default)]
+struct InstalledListeners(HashMap<EventKind, Entity>);
+
+/// An [`EntityEffect`] that installs a boxed event handler on the display
+/// entity during `apply`, replaces it in place on `reapply`, and despawns it
+/// in `raze`.
+pub struct InsertEventListener<F: Send + Sync + 'static + FnMut(&ElementEvent, &mut Cx)> {
+    pub(crate) kind: EventKind,
+    pub(crate) handler: Arc<Mutex<F>>,
+}
+
+impl<F: Send + Sync + 'static + FnMut(&ElementEvent, &mut Cx)> EntityEffect
+    for InsertEventListener<F>
+{
+    type State = ();
+
+    fn apply(&self, cx: &mut Cx, entity: Entity) -> Self::State {
+        if self.kind == EventKind::Key {
+            self.install_key_handler(cx, entity);
+        } else {
+            self.install(cx, entity, None);
+        }
+    }
+
+    fn reapply(&self, cx: &mut Cx, entity: Entity, _state: &mut Self::State) {
+        if self.kind == EventKind::Key {
+            // `KeyHandler` is plain data, not an observer entity, so
+            // overwriting it in place already replaces the old handler.
+            self.install_key_handler(cx, entity);
+            return;
+        }
+        // Replace the previously-installed observer for this kind rather
+        // than stacking a new one on top of it.
+        let previous = cx
+            .world()
+            .get::<InstalledListeners>(entity)
+            .and_then(|listeners| listeners.0.get(&self.kind).copied());
+        self.install(cx, entity, previous);
+    }
+
+    fn raze(&self, world: &mut World, entity: Entity, _state: &mut Self::State) {
+        if self.kind == EventKind::Key {
+            if let Some(mut entity) = world.get_entity_mut(entity) {
+                entity.remove::<KeyHandler>();
+            }
+            return;
+        }
+        let observer = world
+            .get_mut::<InstalledListeners>(entity)
+            .and_then(|mut listeners| listeners.0.remove(&self.kind));
+        if let Some(observer) = observer {
+            if let Some(observer) = world.get_entity_mut(observer) {
+                observer.despawn();
+            }
+        }
+    }
+}
+
+impl<F: Send + Sync + 'static + FnMut(&ElementEvent, &mut Cx)> InsertEventListener<F> {
+    /// Spawn a fresh observer for `self.kind` that calls back into
+    /// `self.handler`, and replace whatever observer was previously recorded
+    /// for this kind on this entity (if any).
+    fn install(&self, cx: &mut Cx, entity: Entity, previous: Option<Entity>) {
+        let owner = cx.owner;
+        let kind = self.kind;
+        let handler = self.handler.clone();
+
+        let observer = match kind {
+            EventKind::Click => {
+                let handler = handler.clone();
+                cx.world_mut()
+                    .spawn(
+                        Observer::new(
+                            move |trigger: Trigger<Pointer<Click>>, world: &mut World| {
+                                let _ = trigger;
+                                let mut cx = Cx::for_owner(world, owner);
+                                handler.lock().unwrap()(&ElementEvent::Click, &mut cx);
+                            },
+                        )
+                        .with_entity(entity),
+                    )
+                    .id()
+            }
+            EventKind::PointerDown => {
+                let handler = handler.clone();
+                cx.world_mut()
+                    .spawn(
+                        Observer::new(
+                            move |trigger: Trigger<Pointer<Down>>, world: &mut World| {
+                                let button = trigger.event().button;
+                                let mut cx = Cx::for_owner(world, owner);
+                                handler.lock().unwrap()(
+                                    &ElementEvent::PointerDown { button },
+                                    &mut cx,
+                                );
+                            },
+                        )
+                        .with_entity(entity),
+                    )
+                    .id()
+            }
+            EventKind::PointerOver => {
+                let handler = handler.clone();
+                cx.world_mut()
+                    .spawn(
+                        Observer::new(
+                            move |trigger: Trigger<Pointer<Over>>, world: &mut World| {
+                                let _ = trigger;
+                                let mut cx = Cx::for_owner(world, owner);
+                                handler.lock().unwrap()(&ElementEvent::PointerOver, &mut cx);
+                            },
+                        )
+                        .with_entity(entity),
+                    )
+                    .id()
+            }
+            EventKind::Key => unreachable!("EventKind::Key is installed via install_key_handler"),
+        };
+
+        if let Some(previous) = previous {
+            if let Some(previous) = cx.world_mut().get_entity_mut(previous) {
+                previous.despawn();
+            }
+        }
+        let mut entity = cx.world_mut().entity_mut(entity);
+        if !entity.contains::<InstalledListeners>() {
+            entity.insert(InstalledListeners::default());
+        }
+        if let Some(mut listeners) = entity.get_mut::<InstalledListeners>() {
+            listeners.0.insert(kind, observer);
+        }
+    }
+
+    /// Insert/overwrite the [`KeyHandler`] component, see its doc comment for
+    /// why `Key` doesn't go through [`InsertEventListener::install`].
+    fn install_key_handler(&self, cx: &mut Cx, entity: Entity) {
+        let handler: Arc<Mutex<dyn FnMut(&ElementEvent, &mut Cx) + Send + Sync>> =
+            self.handler.clone();
+        cx.world_mut().entity_mut(entity).insert(KeyHandler(handler));
+    }
+}
+
+impl<B: Bundle + Default, C: crate::view_tuple::ViewTuple, E: EffectTuple>
+    crate::element::Element<B, C, E>
+{
+    /// Invoke `handler` when the element is clicked.
+    pub fn on_click<F>(
+        self,
+        handler: F,
+    ) -> crate::element::Element<B, C, <E as AppendEffect<InsertEventListener<F>>>::Result>
+    where
+        F: Send + Sync + 'static + FnMut(&ElementEvent, &mut Cx),
+        E: AppendEffect<InsertEventListener<F>>,
+    {
+        self.add_effect(InsertEventListener {
+            kind: EventKind::Click,
+            handler: Arc::new(Mutex::new(handler)),
+        })
+    }
+
+    /// Invoke `handler` when a pointer button goes down over the element.
+    pub fn on_pointer_down<F>(
+        self,
+        handler: F,
+    ) -> crate::element::Element<B, C, <E as AppendEffect<InsertEventListener<F>>>::Result>
+    where
+        F: Send + Sync + 'static + FnMut(&ElementEvent, &mut Cx),
+        E: AppendEffect<InsertEventListener<F>>,
+    {
+        self.add_effect(InsertEventListener {
+            kind: EventKind::PointerDown,
+            handler: Arc::new(Mutex::new(handler)),
+        })
+    }
+
+    /// Invoke `handler` when the pointer enters the element's hit region.
+    pub fn on_pointer_over<F>(
+        self,
+        handler: F,
+    ) -> crate::element::Element<B, C, <E as AppendEffect<InsertEventListener<F>>>::Result>
+    where
+        F: Send + Sync + 'static + FnMut(&ElementEvent, &mut Cx),
+        E: AppendEffect<InsertEventListener<F>>,
+    {
+        self.add_effect(InsertEventListener {
+            kind: EventKind::PointerOver,
+            handler: Arc::new(Mutex::new(handler)),
+        })
+    }
+
+    /// Invoke `handler` for each key pressed while the element is the
+    /// [`FocusedEntity`] — requires [`dispatch_key_events`] to be added to
+    /// the app's `Update` schedule, since `Key` listeners aren't observers.
+    pub fn on_key<F>(
+        self,
+        handler: F,
+    ) -> crate::element::Element<B, C, <E as AppendEffect<InsertEventListener<F>>>::Result>
+    where
+        F: Send + Sync + 'static + FnMut(&ElementEvent, &mut Cx),
+        E: AppendEffect<InsertEventListener<F>>,
+    {
+        self.add_effect(InsertEventListener {
+            kind: EventKind::Key,
+            handler: Arc::new(Mutex::new(handler)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    fn counting_handler(calls: Arc<AtomicUsize>) -> impl FnMut(&ElementEvent, &mut Cx) {
+        move |_event, _cx| {
+            calls.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn dispatch_key_events_only_calls_the_focused_entitys_handler() {
+        let mut world = World::new();
+        world.init_resource::<FocusedEntity>();
+        world.init_resource::<ButtonInput<KeyCode>>();
+
+        let focused = world.spawn_empty().id();
+        let other = world.spawn_empty().id();
+
+        let focused_calls = Arc::new(AtomicUsize::new(0));
+        let other_calls = Arc::new(AtomicUsize::new(0));
+
+        world.entity_mut(focused).insert(KeyHandler(Arc::new(Mutex::new(
+            counting_handler(focused_calls.clone()),
+        ))));
+        world.entity_mut(other).insert(KeyHandler(Arc::new(Mutex::new(
+            counting_handler(other_calls.clone()),
+        ))));
+
+        world.resource_mut::<FocusedEntity>().0 = Some(focused);
+        world.resource_mut::<ButtonInput<KeyCode>>().press(KeyCode::KeyA);
+
+        dispatch_key_events(&mut world);
+
+        assert_eq!(focused_calls.load(Ordering::SeqCst), 1);
+        assert_eq!(other_calls.load(Ordering::SeqCst), 0);
+    }
+}
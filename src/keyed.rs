@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use bevy::prelude::*;
+
+use crate::{cx::Cx, node_span::NodeSpan, view::View, view_tuple::ViewTuple};
+
+/// A stable identity for a child view in a [`KeyedChildren`] list. Any
+/// `Hash + Eq + Clone` type can be used as a key.
+pub trait Key: Hash + Eq + Clone + Send + Sync + 'static {}
+impl<T: Hash + Eq + Clone + Send + Sync + 'static> Key for T {}
+
+/// A list of views, each tagged with a stable [`Key`], that reconciles by key
+/// rather than by position. Reordering, inserting or removing entries only
+/// touches the entities whose keys actually changed, so sibling entities keep
+/// their identity (and any component state, like focus or scroll position)
+/// across rebuilds.
+///
+/// Built via [`Element::keyed_children`](crate::element::Element::keyed_children).
+pub struct KeyedChildren<K: Key, V: View + Clone> {
+    items: Vec<(K, V)>,
+}
+
+impl<K: Key, V: View + Clone> KeyedChildren<K, V> {
+    pub fn new(items: impl IntoIterator<Item = (K, V)>) -> Self {
+        Self {
+            items: items.into_iter().collect(),
+        }
+    }
+}
+
+/// Per-key state: the view used to build it (kept around so an entry that
+/// disappears from the next build can still be razed) plus its `View::State`.
+pub struct KeyedSlot<V: View> {
+    view: V,
+    state: V::State,
+}
+
+/// Persisted state for a [`KeyedChildren`] list: the per-key slots plus the
+/// key order they were last built/rebuilt in, so a rebuild can detect a pure
+/// reorder (same keys, same individual states, different sequence) and still
+/// report a change.
+pub struct KeyedState<K: Key, V: View> {
+    order: Vec<K>,
+    slots: HashMap<K, KeyedSlot<V>>,
+}
+
+impl<K: Key, V: View + Clone> ViewTuple for KeyedChildren<K, V> {
+    type State = KeyedState<K, V>;
+
+    fn build_spans(&self, cx: &mut Cx) -> Self::State {
+        let mut order = Vec::with_capacity(self.items.len());
+        let mut slots = HashMap::with_capacity(self.items.len());
+        for (key, view) in &self.items {
+            let state = view.build(cx);
+            order.push(key.clone());
+            slots.insert(
+                key.clone(),
+                KeyedSlot {
+                    view: view.clone(),
+                    state,
+                },
+            );
+        }
+        KeyedState { order, slots }
+    }
+
+    fn span_nodes(&self, state: &Self::State) -> NodeSpan {
+        NodeSpan::Fragment(
+            self.items
+                .iter()
+                .filter_map(|(key, _)| {
+                    state.slots.get(key).map(|slot| slot.view.nodes(&slot.state))
+                })
+                .collect(),
+        )
+    }
+
+    fn rebuild_spans(&self, cx: &mut Cx, state: &mut Self::State) -> bool {
+        let next_order: Vec<K> = self.items.iter().map(|(key, _)| key.clone()).collect();
+        // A reorder of the same keys must also be reported as a change, even
+        // if every individual child's own `rebuild` reports no change,
+        // otherwise `NodeSpan` ordering gets recomputed but never applied.
+        let mut changed = next_order != state.order;
+
+        let mut next_slots = HashMap::with_capacity(self.items.len());
+        for (key, view) in &self.items {
+            match state.slots.remove(key) {
+                // Key existed before: reuse its entity, just rebuild it.
+                Some(mut slot) => {
+                    changed |= view.rebuild(cx, &mut slot.state);
+                    slot.view = view.clone();
+                    next_slots.insert(key.clone(), slot);
+                }
+                // New key: build a fresh entity for it.
+                None => {
+                    let slot = KeyedSlot {
+                        view: view.clone(),
+                        state: view.build(cx),
+                    };
+                    next_slots.insert(key.clone(), slot);
+                    changed = true;
+                }
+            }
+        }
+
+        // Whatever is left in `state.slots` belongs to keys dropped from the
+        // new sequence; raze them so their entities don't leak.
+        for (_, mut orphan) in state.slots.drain() {
+            orphan.view.raze(cx.world_mut(), &mut orphan.state);
+            changed = true;
+        }
+
+        state.order = next_order;
+        state.slots = next_slots;
+        changed
+    }
+
+    fn raze_spans(&self, world: &mut World, state: &mut Self::State) {
+        for (_, mut slot) in state.slots.drain() {
+            slot.view.raze(world, &mut slot.state);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone)]
+    struct TestLeaf;
+
+    impl View for TestLeaf {
+        type State = Entity;
+
+        fn nodes(&self, state: &Self::State) -> NodeSpan {
+            NodeSpan::Node(*state)
+        }
+
+        fn build(&self, cx: &mut Cx) -> Self::State {
+            cx.world_mut().spawn_empty().id()
+        }
+
+        fn rebuild(&self, _cx: &mut Cx, _state: &mut Self::State) -> bool {
+            false
+        }
+
+        fn raze(&self, world: &mut World, state: &mut Self::State) {
+            world.despawn(*state);
+        }
+    }
+
+    #[test]
+    fn pure_reorder_is_reported_as_a_change() {
+        let mut world = World::new();
+        let owner = world.spawn_empty().id();
+
+        let initial = KeyedChildren::new([('a', TestLeaf), ('b', TestLeaf), ('c', TestLeaf)]);
+        let mut state = {
+            let mut cx = Cx::for_owner(&mut world, owner);
+            initial.build_spans(&mut cx)
+        };
+
+        let entity_a = state.slots[&'a'].state;
+        let entity_b = state.slots[&'b'].state;
+        let entity_c = state.slots[&'c'].state;
+
+        let reordered = KeyedChildren::new([('c', TestLeaf), ('b', TestLeaf), ('a', TestLeaf)]);
+        let changed = {
+            let mut cx = Cx::for_owner(&mut world, owner);
+            reordered.rebuild_spans(&mut cx, &mut state)
+        };
+
+        assert!(changed, "a pure reorder of existing keys must report a change");
+
+        // Reordering must not rebuild the underlying entities.
+        assert_eq!(state.slots[&'a'].state, entity_a);
+        assert_eq!(state.slots[&'b'].state, entity_b);
+        assert_eq!(state.slots[&'c'].state, entity_c);
+
+        match reordered.span_nodes(&state) {
+            NodeSpan::Fragment(nodes) => {
+                let entities: Vec<Entity> = nodes
+                    .into_iter()
+                    .map(|node| match node {
+                        NodeSpan::Node(entity) => entity,
+                        _ => panic!("expected a NodeSpan::Node"),
+                    })
+                    .collect();
+                assert_eq!(entities, vec![entity_c, entity_b, entity_a]);
+            }
+            _ => panic!("expected a NodeSpan::Fragment"),
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use bevy::prelude::*;
+
+/// Size hints an element can feed back onto its display node after its
+/// children have been built, so that content-driven ("intrinsic") sizing is
+/// possible without a second full rebuild. Returned by the closure passed to
+/// [`Element::measured`](crate::element::Element::measured), which
+/// `Element`'s inherent `layout` step evaluates after `build` and after any
+/// `rebuild` where the child set changed or a `measure` closure is set, but
+/// before the frame's taffy layout pass.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LayoutConstraint {
+    pub min_width: Option<Val>,
+    pub min_height: Option<Val>,
+    pub preferred_width: Option<Val>,
+    pub preferred_height: Option<Val>,
+}
+
+impl LayoutConstraint {
+    /// Apply this constraint's fields onto an entity's [`Style`], leaving
+    /// any field left as `None` untouched.
+    pub fn apply(&self, style: &mut Style) {
+        if let Some(min_width) = self.min_width {
+            style.min_width = min_width;
+        }
+        if let Some(min_height) = self.min_height {
+            style.min_height = min_height;
+        }
+        if let Some(preferred_width) = self.preferred_width {
+            style.width = preferred_width;
+        }
+        if let Some(preferred_height) = self.preferred_height {
+            style.height = preferred_height;
+        }
+    }
+}
+
+/// Reads the computed (post-taffy) size of `entity`'s `Node`, if it has been
+/// laid out at least once. Used by a parent's `measure` closure to size
+/// itself to a child's intrinsic content.
+pub fn computed_size(world: &World, entity: Entity) -> Option<Vec2> {
+    world.get::<Node>(entity).map(Node::size)
+}